@@ -0,0 +1,94 @@
+// SPDX-FileCopyrightText: 2025 Eduardo Martinez Martinez <eduardo@monte.blue>
+// SPDX-License-Identifier: AGPL-3.0-only
+
+//! The `/all/feed` route: fetches every configured feed concurrently and
+//! merges their items into a single channel sorted by publication date.
+
+use anyhow::Result;
+use axum::{
+    extract::Query,
+    http::StatusCode,
+    http::header::HeaderMap,
+    response::IntoResponse,
+};
+use chrono::{DateTime, FixedOffset};
+use futures::stream::{self, StreamExt};
+use log::error;
+use rss::{Channel, ChannelBuilder, Item};
+use std::sync::Arc;
+
+use crate::cache::SharedCache;
+use crate::rss_utils::{self, FeedQuery};
+use crate::scraper_engine::{self, CompiledFeed};
+
+const PATH: &str = "/all/feed";
+const TITLE: &str = "Todos los feeds";
+const DESCRIPTION: &str = "Feed agregado con las entradas de todas las fuentes configuradas";
+/// How many sources are scraped at once; keeps one slow upstream from
+/// serializing the rest.
+const CONCURRENCY: usize = 4;
+
+async fn fetch_items(feed: &CompiledFeed, cache: &SharedCache) -> Result<Vec<Item>> {
+    if let Some(body) = cache.get(feed.path()).await {
+        return Ok(Channel::read_from(std::io::Cursor::new(body.as_bytes()))?.items);
+    }
+
+    let channel = scraper_engine::scrape(feed).await?;
+    cache
+        .insert(feed.path().to_string(), channel.to_string())
+        .await;
+    Ok(channel.items)
+}
+
+fn item_date(item: &Item) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc2822(item.pub_date()?).ok()
+}
+
+async fn build_aggregate(feeds: &[Arc<CompiledFeed>], cache: &SharedCache) -> Channel {
+    let per_source = stream::iter(feeds.iter().cloned())
+        .map(|feed| {
+            let cache = cache.clone();
+            async move {
+                fetch_items(&feed, &cache).await.unwrap_or_else(|e| {
+                    error!("Error fetching {} for the aggregate feed: {e}", feed.path());
+                    Vec::new()
+                })
+            }
+        })
+        .buffer_unordered(CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await;
+
+    let mut items: Vec<Item> = per_source.into_iter().flatten().collect();
+    items.sort_by_key(|item| std::cmp::Reverse(item_date(item)));
+
+    ChannelBuilder::default()
+        .title(TITLE)
+        .link(PATH)
+        .description(DESCRIPTION)
+        .items(items)
+        .build()
+}
+
+pub async fn handle(
+    feeds: Arc<Vec<Arc<CompiledFeed>>>,
+    cache: SharedCache,
+    Query(query): Query<FeedQuery>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let channel = build_aggregate(&feeds, &cache).await;
+    match rss_utils::render(channel, &query) {
+        Ok((body, content_type)) => {
+            rss_utils::respond_with_body(content_type, body, &headers, cache.ttl_secs())
+                .map(|resp| resp.into_response())
+                .unwrap_or_else(|e| {
+                    error!("Error building the aggregate RSS response: {e}");
+                    StatusCode::NO_CONTENT.into_response()
+                })
+        }
+        Err(e) => {
+            error!("Error rendering the aggregate RSS response: {e}");
+            StatusCode::NO_CONTENT.into_response()
+        }
+    }
+}
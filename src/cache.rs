@@ -0,0 +1,88 @@
+// SPDX-FileCopyrightText: 2025 Eduardo Martinez Martinez <eduardo@monte.blue>
+// SPDX-License-Identifier: AGPL-3.0-only
+
+//! A small TTL cache shared between the feed handlers so that a burst of
+//! polling feed readers doesn't translate into a scrape per request.
+//!
+//! The current backend is an in-memory `HashMap` behind a `tokio::sync::Mutex`.
+//! The `FeedCache` API is deliberately backend-agnostic so a persistent (e.g.
+//! SQLite-backed) implementation can be swapped in later without touching the
+//! handlers.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::Mutex;
+
+pub type SharedCache = Arc<FeedCache>;
+
+#[derive(Clone)]
+struct CachedFeed {
+    body: String,
+    fetched_at: SystemTime,
+}
+
+pub struct FeedCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, CachedFeed>>,
+}
+
+impl FeedCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// TTL in whole seconds, used to tell clients how long they may cache a
+    /// response via `Cache-Control: max-age=`.
+    pub fn ttl_secs(&self) -> u64 {
+        self.ttl.as_secs()
+    }
+
+    /// Returns the cached body for `key` if it is still within the TTL window.
+    pub async fn get(&self, key: &str) -> Option<String> {
+        let entries = self.entries.lock().await;
+        let entry = entries.get(key)?;
+        if entry.fetched_at.elapsed().ok()? < self.ttl {
+            Some(entry.body.clone())
+        } else {
+            None
+        }
+    }
+
+    pub async fn insert(&self, key: String, body: String) {
+        let mut entries = self.entries.lock().await;
+        entries.insert(
+            key,
+            CachedFeed {
+                body,
+                fetched_at: SystemTime::now(),
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn miss_then_hit_then_expiry() {
+        let cache = FeedCache::new(Duration::from_millis(50));
+        assert!(cache.get("feed").await.is_none());
+
+        cache.insert("feed".to_string(), "<rss/>".to_string()).await;
+        assert_eq!(cache.get("feed").await.as_deref(), Some("<rss/>"));
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(cache.get("feed").await.is_none());
+    }
+
+    #[test]
+    fn ttl_secs_reports_configured_ttl() {
+        let cache = FeedCache::new(Duration::from_secs(3600));
+        assert_eq!(cache.ttl_secs(), 3600);
+    }
+}
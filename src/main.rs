@@ -2,35 +2,56 @@
 // SPDX-License-Identifier: AGPL-3.0-only
 
 use anyhow::Result;
-use axum::{Router, routing::get};
+use axum::{Router, extract::State, http::header::HeaderMap, routing::get};
 use log::{error, info};
 use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
-mod isabel;
+mod all_feed;
+mod cache;
 mod rss_utils;
-mod verde;
+mod scraper_engine;
+
+use cache::SharedCache;
 
 const DEFAULT_ADDR: &str = "127.0.0.1:3101";
+const DEFAULT_TTL_SECS: u64 = 300;
 
 const HELP_MESSAGE: &str = r#"Custom RSS feed for web pages that don't have them.
 
-Current feeds:
+Current feeds (overridden by -c/--config if given):
   /blog-isabel/feed       https://marmenormarmayor.es/El-blog-de-Isabel/index.html
   /verde/blog/feed        https://elclickverde.com/blog
   /verde/reportajes/feed  https://elclickverde.com/reportajes
+  /all/feed               every feed above, merged and sorted by date
 
 Usage:
-  $ custom-rss [-a <listening-addr>] [-h]
+  $ custom-rss [-a <listening-addr>] [-t <ttl-seconds>] [-c <config-file>] [-h]
 
   -a <listening-addr> selects the IP and port that the server will listen to.
      Example 192.168.0.1:2612
      Default localhost:3101
+  -t <ttl-seconds> how long a scraped feed is served from cache before being
+     re-scraped.
+     Default 300
+  -c <config-file> a TOML file declaring the feeds to scrape, see
+     `scraper_engine::FeedConfig`. Defaults to the feeds above when omitted.
   -h print help"#;
 
-fn parse_args() -> Result<SocketAddr> {
+struct Args {
+    addr: SocketAddr,
+    ttl: Duration,
+    config: Option<PathBuf>,
+}
+
+fn parse_args() -> Result<Args> {
     use lexopt::prelude::*;
 
     let mut addr: SocketAddr = DEFAULT_ADDR.parse()?;
+    let mut ttl = Duration::from_secs(DEFAULT_TTL_SECS);
+    let mut config = None;
     let mut parser = lexopt::Parser::from_env();
 
     while let Some(arg) = parser.next()? {
@@ -38,6 +59,12 @@ fn parse_args() -> Result<SocketAddr> {
             Short('a') | Long("addr") => {
                 addr = parser.value()?.parse()?;
             }
+            Short('t') | Long("ttl") => {
+                ttl = Duration::from_secs(parser.value()?.parse()?);
+            }
+            Short('c') | Long("config") => {
+                config = Some(PathBuf::from(parser.value()?));
+            }
             Short('h') | Long("help") => {
                 println!("{HELP_MESSAGE}");
                 std::process::exit(0);
@@ -46,17 +73,45 @@ fn parse_args() -> Result<SocketAddr> {
         }
     }
 
-    Ok(addr)
+    Ok(Args { addr, ttl, config })
+}
+
+fn load_config(path: Option<&PathBuf>) -> Result<scraper_engine::EngineConfig> {
+    match path {
+        Some(path) => {
+            let content = std::fs::read_to_string(path)?;
+            Ok(toml::from_str(&content)?)
+        }
+        None => Ok(scraper_engine::default_config()),
+    }
 }
 
 async fn run() -> Result<()> {
     let args = parse_args()?;
-    info!("Listening on address: {args}");
-    let app = Router::new()
-        .route("/blog-isabel/feed", get(isabel::rss))
-        .route("/verde/blog/feed", get(verde::blog_rss))
-        .route("/verde/reportajes/feed", get(verde::reportajes_rss));
-    let listener = tokio::net::TcpListener::bind(args).await?;
+    info!("Listening on address: {}", args.addr);
+
+    let config = load_config(args.config.as_ref())?;
+    let feeds: Vec<Arc<scraper_engine::CompiledFeed>> = scraper_engine::compile_feeds(config)?
+        .into_iter()
+        .map(Arc::new)
+        .collect();
+    let all_feeds = Arc::new(feeds.clone());
+
+    let cache = Arc::new(cache::FeedCache::new(args.ttl));
+    let app = scraper_engine::register_routes(Router::new(), &feeds)
+        .route(
+            "/all/feed",
+            get(
+                move |State(cache): State<SharedCache>,
+                      query: axum::extract::Query<rss_utils::FeedQuery>,
+                      headers: HeaderMap| {
+                    let all_feeds = all_feeds.clone();
+                    async move { all_feed::handle(all_feeds, cache, query, headers).await }
+                },
+            ),
+        )
+        .with_state(cache);
+    let listener = tokio::net::TcpListener::bind(args.addr).await?;
     axum::serve(listener, app).await?;
     Ok(())
 }
@@ -5,17 +5,354 @@ use anyhow::Result;
 use axum::{
     http::{
         StatusCode,
-        header::{CONTENT_TYPE, HeaderMap},
+        header::{CACHE_CONTROL, CONTENT_TYPE, ETAG, HeaderMap, IF_NONE_MATCH},
     },
     response::IntoResponse,
 };
+use chrono::DateTime;
 use rss::Channel;
+use serde::Deserialize;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 
-const XML_TYPE: &str = "application/xml";
+const DEFAULT_LIMIT: usize = 20;
+
+/// Output serialization selected via `?format=`.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    #[default]
+    Rss,
+    Atom,
+    Json,
+}
+
+impl Format {
+    fn content_type(self) -> &'static str {
+        match self {
+            Format::Rss => "application/xml",
+            Format::Atom => "application/atom+xml",
+            Format::Json => "application/feed+json",
+        }
+    }
+}
+
+/// Query parameters accepted by every feed route: `?limit=N&format=atom`.
+#[derive(Debug, Deserialize)]
+pub struct FeedQuery {
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub format: Format,
+}
+
+fn etag_for(body: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    body.hash(&mut hasher);
+    format!("\"{:016x}\"", hasher.finish())
+}
+
+fn if_none_match_hits(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get(IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.split(',').any(|candidate| candidate.trim() == etag))
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// RFC 4287 §4.1.1 requires exactly one feed-level `atom:updated`. We take
+/// the newest item `pub_date`, falling back to the Unix epoch (rather than
+/// the current time) so the same channel always renders the same feed.
+fn feed_updated(ch: &Channel) -> String {
+    ch.items
+        .iter()
+        .filter_map(|item| item.pub_date().and_then(|d| DateTime::parse_from_rfc2822(d).ok()))
+        .max()
+        .unwrap_or_else(|| DateTime::UNIX_EPOCH.into())
+        .to_rfc3339()
+}
+
+fn render_atom(ch: &Channel) -> String {
+    let mut out = String::from(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    out.push('\n');
+    out.push_str(r#"<feed xmlns="http://www.w3.org/2005/Atom">"#);
+    out.push('\n');
+    out.push_str(&format!("  <title>{}</title>\n", escape_xml(&ch.title)));
+    out.push_str(&format!(
+        "  <link href=\"{}\"/>\n",
+        escape_xml(&ch.link)
+    ));
+    out.push_str(&format!("  <id>{}</id>\n", escape_xml(&ch.link)));
+    out.push_str(&format!(
+        "  <subtitle>{}</subtitle>\n",
+        escape_xml(&ch.description)
+    ));
+    out.push_str(&format!("  <updated>{}</updated>\n", feed_updated(ch)));
+
+    for item in &ch.items {
+        out.push_str("  <entry>\n");
+        if let Some(title) = item.title() {
+            out.push_str(&format!("    <title>{}</title>\n", escape_xml(title)));
+        }
+        if let Some(link) = item.link() {
+            out.push_str(&format!("    <link href=\"{}\"/>\n", escape_xml(link)));
+        }
+        let id = item
+            .guid()
+            .map(|g| g.value())
+            .or_else(|| item.link())
+            .unwrap_or_default();
+        out.push_str(&format!("    <id>{}</id>\n", escape_xml(id)));
+        if let Some(updated) = item
+            .pub_date()
+            .and_then(|d| DateTime::parse_from_rfc2822(d).ok())
+        {
+            out.push_str(&format!("    <updated>{}</updated>\n", updated.to_rfc3339()));
+        }
+        if let Some(description) = item.description() {
+            out.push_str(&format!(
+                "    <summary>{}</summary>\n",
+                escape_xml(description)
+            ));
+        }
+        // RFC 4287 §4.1.2 requires every entry to carry an `atom:author`
+        // unless the feed itself supplies one; we have neither a feed-level
+        // author nor `atom:source`, so fall back to the channel title when
+        // an item has no `dc:creator`.
+        let author = item
+            .dublin_core_ext()
+            .and_then(|dc| dc.creators().first())
+            .map(String::as_str)
+            .unwrap_or(&ch.title);
+        out.push_str(&format!(
+            "    <author><name>{}</name></author>\n",
+            escape_xml(author)
+        ));
+        out.push_str("  </entry>\n");
+    }
+
+    out.push_str("</feed>\n");
+    out
+}
+
+fn render_json_feed(ch: &Channel) -> Result<String> {
+    let items: Vec<serde_json::Value> = ch
+        .items
+        .iter()
+        .map(|item| {
+            let id = item
+                .guid()
+                .map(|g| g.value().to_string())
+                .or_else(|| item.link().map(str::to_string))
+                .unwrap_or_default();
+            let date_published = item
+                .pub_date()
+                .and_then(|d| DateTime::parse_from_rfc2822(d).ok())
+                .map(|dt| dt.to_rfc3339());
+            let author = item
+                .dublin_core_ext()
+                .and_then(|dc| dc.creators().first())
+                .map(|name| serde_json::json!({ "name": name }));
+            let tags: Vec<&str> = item.categories().iter().map(|c| c.name()).collect();
+
+            serde_json::json!({
+                "id": id,
+                "url": item.link(),
+                "title": item.title(),
+                "content_text": item.description(),
+                "date_published": date_published,
+                "author": author,
+                "tags": tags,
+            })
+        })
+        .collect();
+
+    let feed = serde_json::json!({
+        "version": "https://jsonfeed.org/version/1.1",
+        "title": ch.title,
+        "home_page_url": ch.link,
+        "description": ch.description,
+        "items": items,
+    });
+
+    Ok(serde_json::to_string(&feed)?)
+}
+
+/// Truncates `ch` to `query.limit` items (`DEFAULT_LIMIT` when unset) and
+/// serializes it in the requested `query.format`.
+pub fn render(mut ch: Channel, query: &FeedQuery) -> Result<(String, &'static str)> {
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+    ch.items.truncate(limit);
+
+    let body = match query.format {
+        Format::Rss => ch.to_string(),
+        Format::Atom => render_atom(&ch),
+        Format::Json => render_json_feed(&ch)?,
+    };
+
+    Ok((body, query.format.content_type()))
+}
+
+/// Builds the conditional-GET-aware response for an already-rendered feed
+/// body, whether it was just scraped or served out of the [`crate::cache`].
+/// `ttl_secs` should be the cache's own TTL, so the `Cache-Control` header we
+/// hand to clients matches how long we'll actually keep serving this body.
+pub fn respond_with_body(
+    content_type: &str,
+    body: String,
+    req_headers: &HeaderMap,
+    ttl_secs: u64,
+) -> Result<impl IntoResponse> {
+    let etag = etag_for(&body);
+    let cache_control = format!("max-age={ttl_secs}");
+
+    if if_none_match_hits(req_headers, &etag) {
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, etag.parse()?);
+        headers.insert(CACHE_CONTROL, cache_control.parse()?);
+        return Ok((StatusCode::NOT_MODIFIED, headers, String::new()));
+    }
 
-pub fn make_rss(ch: Channel) -> Result<impl IntoResponse> {
     let mut headers = HeaderMap::new();
-    let xml = XML_TYPE.parse()?;
-    headers.insert(CONTENT_TYPE, xml);
-    Ok((StatusCode::OK, headers, ch.to_string()))
+    headers.insert(CONTENT_TYPE, content_type.parse()?);
+    headers.insert(ETAG, etag.parse()?);
+    headers.insert(CACHE_CONTROL, cache_control.parse()?);
+    Ok((StatusCode::OK, headers, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn etag_is_stable_for_same_body() {
+        assert_eq!(etag_for("hello"), etag_for("hello"));
+        assert_ne!(etag_for("hello"), etag_for("world"));
+    }
+
+    #[test]
+    fn if_none_match_hits_exact_match() {
+        let mut headers = HeaderMap::new();
+        headers.insert(IF_NONE_MATCH, "\"abc123\"".parse().unwrap());
+        assert!(if_none_match_hits(&headers, "\"abc123\""));
+        assert!(!if_none_match_hits(&headers, "\"other\""));
+    }
+
+    #[test]
+    fn render_truncates_to_limit() {
+        let ch = Channel {
+            items: vec![rss::Item::default(); 5],
+            ..Default::default()
+        };
+        let query = FeedQuery {
+            limit: Some(2),
+            format: Format::Rss,
+        };
+        let (body, content_type) = render(ch, &query).unwrap();
+        assert_eq!(content_type, "application/xml");
+        assert_eq!(body.matches("<item>").count(), 2);
+    }
+
+    #[test]
+    fn render_defaults_to_twenty_items() {
+        let ch = Channel {
+            items: vec![rss::Item::default(); 30],
+            ..Default::default()
+        };
+        let query = FeedQuery {
+            limit: None,
+            format: Format::Rss,
+        };
+        let (body, _) = render(ch, &query).unwrap();
+        assert_eq!(body.matches("<item>").count(), DEFAULT_LIMIT);
+    }
+
+    fn sample_channel() -> Channel {
+        let older = rss::ItemBuilder::default()
+            .title(Some("Older post".to_string()))
+            .link(Some("https://example.com/older".to_string()))
+            .pub_date(Some("Wed, 01 Jan 2025 12:00:00 +0100".to_string()))
+            .build();
+        let newer = rss::ItemBuilder::default()
+            .title(Some("Newer post".to_string()))
+            .link(Some("https://example.com/newer".to_string()))
+            .pub_date(Some("Fri, 10 Jan 2025 12:00:00 +0100".to_string()))
+            .build();
+        Channel {
+            title: "Sample feed".to_string(),
+            link: "https://example.com".to_string(),
+            description: "A sample feed".to_string(),
+            items: vec![older, newer],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn render_atom_includes_feed_level_updated() {
+        let query = FeedQuery {
+            limit: None,
+            format: Format::Atom,
+        };
+        let (body, content_type) = render(sample_channel(), &query).unwrap();
+        assert_eq!(content_type, "application/atom+xml");
+        assert_eq!(body.matches("<entry>").count(), 2);
+        assert!(body.contains("<updated>2025-01-10T12:00:00+01:00</updated>"));
+        assert!(body.contains("<title>Sample feed</title>"));
+        // No item carries a dc:creator, so every entry falls back to the
+        // channel title as its atom:author.
+        assert_eq!(
+            body.matches("<author><name>Sample feed</name></author>").count(),
+            2
+        );
+    }
+
+    #[test]
+    fn render_atom_uses_dc_creator_as_entry_author() {
+        use rss::extension::dublincore::DublinCoreExtensionBuilder;
+
+        let item = rss::ItemBuilder::default()
+            .title(Some("Authored post".to_string()))
+            .link(Some("https://example.com/authored".to_string()))
+            .dublin_core_ext(Some(
+                DublinCoreExtensionBuilder::default()
+                    .creators(vec!["Jane Doe".to_string()])
+                    .build(),
+            ))
+            .build();
+        let ch = Channel {
+            title: "Sample feed".to_string(),
+            items: vec![item],
+            ..Default::default()
+        };
+        let query = FeedQuery {
+            limit: None,
+            format: Format::Atom,
+        };
+        let (body, _) = render(ch, &query).unwrap();
+        assert!(body.contains("<author><name>Jane Doe</name></author>"));
+    }
+
+    #[test]
+    fn render_json_feed_has_required_fields() {
+        let query = FeedQuery {
+            limit: None,
+            format: Format::Json,
+        };
+        let (body, content_type) = render(sample_channel(), &query).unwrap();
+        assert_eq!(content_type, "application/feed+json");
+        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(parsed["version"], "https://jsonfeed.org/version/1.1");
+        assert_eq!(parsed["title"], "Sample feed");
+        let items = parsed["items"].as_array().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0]["title"], "Older post");
+        assert_eq!(items[0]["url"], "https://example.com/older");
+    }
 }
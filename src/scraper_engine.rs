@@ -0,0 +1,481 @@
+// SPDX-FileCopyrightText: 2025 Eduardo Martinez Martinez <eduardo@monte.blue>
+// SPDX-License-Identifier: AGPL-3.0-only
+
+//! A declarative, config-driven replacement for the old `isabel`/`verde`
+//! modules: every feed is now a [`FeedConfig`] entry describing which CSS
+//! selectors to scrape and how to parse its date, instead of a hand-written
+//! function per site. Adding a new site means adding a config entry, not
+//! recompiling.
+
+use anyhow::{Result, anyhow};
+use axum::{
+    Router,
+    extract::{Query, State},
+    http::{StatusCode, header::HeaderMap},
+    response::IntoResponse,
+    routing::get,
+};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+use chrono_tz::Europe::Madrid;
+use log::error;
+use rss::extension::dublincore::{DublinCoreExtension, DublinCoreExtensionBuilder};
+use rss::{Category, Channel, ChannelBuilder, Guid, ItemBuilder};
+use scraper::{Html, Selector};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, LazyLock};
+
+use crate::cache::SharedCache;
+use crate::rss_utils::{self, FeedQuery};
+
+#[derive(Debug, Default, Deserialize)]
+pub struct EngineConfig {
+    #[serde(default)]
+    pub feeds: Vec<FeedConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeedConfig {
+    /// Axum route, e.g. `/blog-isabel/feed`.
+    pub path: String,
+    /// Page that gets scraped.
+    pub source_url: String,
+    pub channel_title: String,
+    pub channel_link: String,
+    pub channel_description: String,
+    /// Prefix prepended to a scraped `href` to form an absolute item link.
+    pub link_base: String,
+    pub entry_selector: String,
+    pub title_selector: String,
+    pub link_selector: String,
+    pub description_selector: String,
+    pub date_selector: String,
+    /// `chrono` format string the date is parsed with, after `month_names`
+    /// substitution (e.g. `"%d de %m de %Y"` or `"%Y-%m-%d"`).
+    pub date_format: String,
+    /// Maps a locale month token (`"enero"`, `"Ene."`) found in the scraped
+    /// date string to its two-digit numeric form, so `date_format` can stay
+    /// purely numeric.
+    #[serde(default)]
+    pub month_names: HashMap<String, String>,
+    /// Selector for an optional per-item author, exposed as `dc:creator`.
+    #[serde(default)]
+    pub author_selector: Option<String>,
+    /// Static `<category>` value applied to every item from this feed.
+    #[serde(default)]
+    pub category: Option<String>,
+    /// When set, `description_selector` matches a container element and
+    /// this is the 0-based index, among *all* `<p>` descendants of that
+    /// container in document order, to use as the description. Needed when
+    /// the real paragraph isn't a direct child of the container, so
+    /// `:nth-of-type` on `description_selector` alone can't express "the
+    /// Nth matching `<p>` anywhere inside it".
+    #[serde(default)]
+    pub description_p_index: Option<usize>,
+}
+
+static P_SELECTOR: LazyLock<Selector> = LazyLock::new(|| Selector::parse("p").unwrap());
+
+#[derive(Debug)]
+pub struct CompiledFeed {
+    config: FeedConfig,
+    entry_selector: Selector,
+    title_selector: Selector,
+    link_selector: Selector,
+    description_selector: Selector,
+    date_selector: Selector,
+    author_selector: Option<Selector>,
+}
+
+impl CompiledFeed {
+    pub(crate) fn path(&self) -> &str {
+        &self.config.path
+    }
+
+    fn compile(config: FeedConfig) -> Result<Self> {
+        let parse = |selector: &str| {
+            Selector::parse(selector)
+                .map_err(|e| anyhow!("invalid selector '{selector}' in '{}': {e:?}", config.path))
+        };
+
+        let author_selector = config
+            .author_selector
+            .as_deref()
+            .map(parse)
+            .transpose()?;
+
+        Ok(Self {
+            entry_selector: parse(&config.entry_selector)?,
+            title_selector: parse(&config.title_selector)?,
+            link_selector: parse(&config.link_selector)?,
+            description_selector: parse(&config.description_selector)?,
+            date_selector: parse(&config.date_selector)?,
+            author_selector,
+            config,
+        })
+    }
+}
+
+/// The aggregated endpoint `main.rs` always registers itself; no per-feed
+/// config entry may claim it.
+const RESERVED_PATH: &str = "/all/feed";
+
+/// Compiles every feed in `config`, failing fast on the first invalid
+/// selector or duplicate/reserved route so a typo in the config file is
+/// reported as an error rather than as an `axum::Router::route` panic while
+/// the routes are registered.
+pub fn compile_feeds(config: EngineConfig) -> Result<Vec<CompiledFeed>> {
+    let mut seen = std::collections::HashSet::new();
+    for feed in &config.feeds {
+        if feed.path == RESERVED_PATH {
+            return Err(anyhow!(
+                "feed path '{}' is reserved for the aggregated endpoint",
+                feed.path
+            ));
+        }
+        if !seen.insert(feed.path.as_str()) {
+            return Err(anyhow!("duplicate feed path '{}' in config", feed.path));
+        }
+    }
+
+    config
+        .feeds
+        .into_iter()
+        .map(CompiledFeed::compile)
+        .collect()
+}
+
+/// The three feeds this server shipped with before the config file existed,
+/// used when no `-c/--config` flag is given.
+pub fn default_config() -> EngineConfig {
+    EngineConfig {
+        feeds: vec![
+            FeedConfig {
+                path: "/blog-isabel/feed".to_string(),
+                source_url: "https://marmenormarmayor.es/El-blog-de-Isabel/archive.html"
+                    .to_string(),
+                channel_title: "El blog de Isabel".to_string(),
+                channel_link: "https://marmenormarmayor.es/El-blog-de-Isabel/".to_string(),
+                channel_description: "Últimas entradas del blog de Isabel".to_string(),
+                link_base: "https://marmenormarmayor.es/El-blog-de-Isabel/".to_string(),
+                entry_selector: ".blogsection".to_string(),
+                title_selector: "h3.blogtitle a".to_string(),
+                link_selector: "h3.blogtitle a".to_string(),
+                description_selector: ".blogcontent".to_string(),
+                date_selector: ".blogdate".to_string(),
+                date_format: "%d de %m de %Y".to_string(),
+                month_names: spanish_month_names(),
+                author_selector: None,
+                category: Some("Blog".to_string()),
+                description_p_index: None,
+            },
+            FeedConfig {
+                path: "/verde/blog/feed".to_string(),
+                source_url: "https://elclickverde.com/blog".to_string(),
+                channel_title: "Blog | elclickverde".to_string(),
+                channel_link: "https://elclickverde.com/blog".to_string(),
+                channel_description: "Últimas entradas del blog de elclickverde".to_string(),
+                link_base: "https://elclickverde.com".to_string(),
+                entry_selector: ".views-row".to_string(),
+                title_selector: ".group-header .field__item.even h2 a".to_string(),
+                link_selector: ".group-header .field__item.even h2 a".to_string(),
+                description_selector: ".group-right p:not(.rteright)".to_string(),
+                date_selector: ".group-right p.rteright span".to_string(),
+                date_format: "%Y-%m-%d".to_string(),
+                month_names: HashMap::new(),
+                author_selector: None,
+                category: Some("Blog".to_string()),
+                description_p_index: None,
+            },
+            FeedConfig {
+                path: "/verde/reportajes/feed".to_string(),
+                source_url: "https://elclickverde.com/reportajes".to_string(),
+                channel_title: "Reportajes | elclickverde".to_string(),
+                channel_link: "https://elclickverde.com/reportajes".to_string(),
+                channel_description: "Últimos reportajes de elclickverde".to_string(),
+                link_base: "https://elclickverde.com".to_string(),
+                entry_selector: ".views-row".to_string(),
+                title_selector: r#"div.field__item.even[property="dc:title"] h2 a"#.to_string(),
+                link_selector: r#"div.field__item.even[property="dc:title"] h2 a"#.to_string(),
+                description_selector: r#"div.field__item.even[property="content:encoded"]"#
+                    .to_string(),
+                date_selector: "div.field.field--name-post-date div.field__item.even".to_string(),
+                date_format: "%d %m %Y".to_string(),
+                month_names: spanish_month_abbreviations(),
+                author_selector: Some(
+                    r#"div.field__item.even[property="dc:creator"]"#.to_string(),
+                ),
+                category: Some("Reportajes".to_string()),
+                description_p_index: Some(1),
+            },
+        ],
+    }
+}
+
+fn spanish_month_names() -> HashMap<String, String> {
+    [
+        ("enero", "01"),
+        ("febrero", "02"),
+        ("marzo", "03"),
+        ("abril", "04"),
+        ("mayo", "05"),
+        ("junio", "06"),
+        ("julio", "07"),
+        ("agosto", "08"),
+        ("septiembre", "09"),
+        ("octubre", "10"),
+        ("noviembre", "11"),
+        ("diciembre", "12"),
+    ]
+    .into_iter()
+    .map(|(name, num)| (name.to_string(), num.to_string()))
+    .collect()
+}
+
+fn spanish_month_abbreviations() -> HashMap<String, String> {
+    [
+        ("Ene.", "01"),
+        ("Feb.", "02"),
+        ("Mar.", "03"),
+        ("Abr.", "04"),
+        ("Mayo.", "05"),
+        ("Jun.", "06"),
+        ("Jul.", "07"),
+        ("Ago.", "08"),
+        ("Sep.", "09"),
+        ("Oct.", "10"),
+        ("Nov.", "11"),
+        ("Dic.", "12"),
+    ]
+    .into_iter()
+    .map(|(name, num)| (name.to_string(), num.to_string()))
+    .collect()
+}
+
+fn parse_date(date_raw: &str, feed: &CompiledFeed) -> Result<String> {
+    let mut normalized = date_raw.to_string();
+    for (name, num) in &feed.config.month_names {
+        normalized = normalized.replace(name, num);
+    }
+    let normalized = normalized
+        .trim_start_matches(|c: char| !c.is_ascii_digit())
+        .trim();
+
+    let date = NaiveDate::parse_from_str(normalized, &feed.config.date_format).map_err(|e| {
+        anyhow!(
+            "Unable to parse date '{date_raw}' with format '{}': {e}",
+            feed.config.date_format
+        )
+    })?;
+    let time = NaiveTime::from_hms_opt(12, 0, 0).unwrap_or_default();
+    match NaiveDateTime::new(date, time).and_local_timezone(Madrid) {
+        chrono::LocalResult::Single(dt) => Ok(dt.to_rfc2822()),
+        _ => Err(anyhow!("Invalid timezone")),
+    }
+}
+
+const DC_NAMESPACE_URI: &str = "http://purl.org/dc/elements/1.1/";
+
+pub(crate) async fn scrape(feed: &CompiledFeed) -> Result<Channel> {
+    let content = reqwest::get(&feed.config.source_url).await?.text().await?;
+    let document = Html::parse_document(&content);
+
+    let mut channel = ChannelBuilder::default()
+        .title(feed.config.channel_title.clone())
+        .link(feed.config.channel_link.clone())
+        .description(feed.config.channel_description.clone())
+        .namespaces(BTreeMap::from([(
+            "dc".to_string(),
+            DC_NAMESPACE_URI.to_string(),
+        )]))
+        .build();
+
+    for element in document.select(&feed.entry_selector) {
+        let title = element
+            .select(&feed.title_selector)
+            .next()
+            .map(|e| e.text().collect::<String>().trim().to_string())
+            .ok_or_else(|| anyhow!("Unable to parse title"))?;
+
+        let link = element
+            .select(&feed.link_selector)
+            .next()
+            .and_then(|e| e.value().attr("href"))
+            .map(|href| format!("{}{}", feed.config.link_base, href))
+            .ok_or_else(|| anyhow!("Unable to parse link"))?;
+
+        let description = {
+            let container = element
+                .select(&feed.description_selector)
+                .next()
+                .ok_or_else(|| anyhow!("Unable to parse description"))?;
+
+            match feed.config.description_p_index {
+                Some(idx) => container
+                    .select(&P_SELECTOR)
+                    .nth(idx)
+                    .map(|e| e.text().collect::<String>().trim().to_string())
+                    .ok_or_else(|| anyhow!("Unable to extract description"))?,
+                None => container.text().collect::<String>().trim().to_string(),
+            }
+        };
+
+        let date_raw = element
+            .select(&feed.date_selector)
+            .next()
+            .map(|e| e.text().collect::<String>().trim().to_string())
+            .ok_or_else(|| anyhow!("Unable to parse date"))?;
+        let date = parse_date(&date_raw, feed)?;
+
+        let author = feed.author_selector.as_ref().and_then(|selector| {
+            element
+                .select(selector)
+                .next()
+                .map(|e| e.text().collect::<String>().trim().to_string())
+        });
+
+        // DCMI recommends `dc:date` be expressed as W3C-DTF/ISO-8601, unlike
+        // the RFC-2822 form RSS's own `pub_date` requires, so reparse here
+        // rather than reusing `date` as-is.
+        let dc_date = DateTime::parse_from_rfc2822(&date)
+            .map(|dt| dt.to_rfc3339())
+            .unwrap_or_else(|_| date.clone());
+
+        let dublin_core: DublinCoreExtension = DublinCoreExtensionBuilder::default()
+            .creators(author.into_iter().collect::<Vec<_>>())
+            .dates(vec![dc_date])
+            .build();
+
+        let categories = feed
+            .config
+            .category
+            .as_ref()
+            .map(|name| {
+                vec![Category {
+                    name: name.clone(),
+                    domain: None,
+                }]
+            })
+            .unwrap_or_default();
+
+        let item = ItemBuilder::default()
+            .title(title)
+            .link(link.clone())
+            .description(description)
+            .guid(Some(Guid {
+                value: link,
+                permalink: true,
+            }))
+            .pub_date(date)
+            .dublin_core_ext(Some(dublin_core))
+            .categories(categories)
+            .build();
+
+        channel.items.push(item);
+    }
+
+    Ok(channel)
+}
+
+async fn cached_body(feed: &CompiledFeed, cache: &SharedCache) -> Result<String> {
+    if let Some(body) = cache.get(&feed.config.path).await {
+        return Ok(body);
+    }
+
+    let body = scrape(feed).await?.to_string();
+    cache.insert(feed.config.path.clone(), body.clone()).await;
+    Ok(body)
+}
+
+async fn handle(
+    feed: Arc<CompiledFeed>,
+    cache: SharedCache,
+    Query(query): Query<FeedQuery>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let rendered = match cached_body(&feed, &cache).await {
+        Ok(body) => Channel::read_from(std::io::Cursor::new(body.as_bytes()))
+            .map_err(anyhow::Error::from)
+            .and_then(|ch| rss_utils::render(ch, &query)),
+        Err(e) => Err(e),
+    };
+
+    match rendered {
+        Ok((body, content_type)) => {
+            rss_utils::respond_with_body(content_type, body, &headers, cache.ttl_secs())
+                .map(|resp| resp.into_response())
+                .unwrap_or_else(|e| {
+                    error!("Error building the RSS response for {}: {e}", feed.config.path);
+                    StatusCode::NO_CONTENT.into_response()
+                })
+        }
+        Err(e) => {
+            error!("Error scraping {}: {e}", feed.config.path);
+            StatusCode::NO_CONTENT.into_response()
+        }
+    }
+}
+
+/// Registers one axum route per compiled feed, sharing `cache` as state.
+pub fn register_routes(
+    mut router: Router<SharedCache>,
+    feeds: &[Arc<CompiledFeed>],
+) -> Router<SharedCache> {
+    for feed in feeds {
+        let feed = feed.clone();
+        let path = feed.config.path.clone();
+        router = router.route(
+            &path,
+            get(
+                move |State(cache): State<SharedCache>,
+                      query: Query<FeedQuery>,
+                      headers: HeaderMap| {
+                    let feed = feed.clone();
+                    async move { handle(feed, cache, query, headers).await }
+                },
+            ),
+        );
+    }
+    router
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_config_compiles() {
+        let feeds = compile_feeds(default_config()).expect("default config selectors are valid");
+        assert_eq!(feeds.len(), 3);
+    }
+
+    #[test]
+    fn compile_feeds_rejects_duplicate_paths() {
+        let mut config = default_config();
+        config.feeds[1].path = config.feeds[0].path.clone();
+        let err = compile_feeds(config).expect_err("duplicate paths must be rejected");
+        assert!(err.to_string().contains("duplicate feed path"));
+    }
+
+    #[test]
+    fn compile_feeds_rejects_reserved_all_feed_path() {
+        let mut config = default_config();
+        config.feeds[0].path = "/all/feed".to_string();
+        let err = compile_feeds(config).expect_err("reserved path must be rejected");
+        assert!(err.to_string().contains("reserved"));
+    }
+
+    #[test]
+    fn parse_date_handles_long_spanish_months() {
+        let feed = CompiledFeed::compile(default_config().feeds.remove(0)).unwrap();
+        let parsed = parse_date("Publicado el 12 de enero de 2025", &feed).unwrap();
+        assert!(parsed.starts_with("Sun, 12 Jan 2025"));
+    }
+
+    #[test]
+    fn parse_date_handles_abbreviated_spanish_months() {
+        let feed = CompiledFeed::compile(default_config().feeds.remove(2)).unwrap();
+        let parsed = parse_date("12 Ene. 2025", &feed).unwrap();
+        assert!(parsed.starts_with("Sun, 12 Jan 2025"));
+    }
+}